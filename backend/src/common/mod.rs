@@ -0,0 +1,2 @@
+pub mod pagination;
+pub mod password;
@@ -0,0 +1,40 @@
+use super::super::handlers::Error;
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+/// Query-guard for collection endpoints, e.g. `?offset=40&limit=20`. Both
+/// fields are optional; a missing `limit` falls back to `DEFAULT_LIMIT` and
+/// an out-of-range one is rejected rather than silently clamped, so clients
+/// notice a bad request instead of quietly getting fewer rows than asked.
+#[derive(FromForm, Debug, Clone, Copy)]
+pub struct Pagination {
+    offset: Option<i64>,
+    limit: Option<i64>,
+}
+
+impl Pagination {
+    pub fn offset(&self) -> Result<i64, Error> {
+        match self.offset {
+            Some(offset) if offset < 0 => Err(Error::Params),
+            Some(offset) => Ok(offset),
+            None => Ok(0),
+        }
+    }
+
+    pub fn limit(&self) -> Result<i64, Error> {
+        match self.limit {
+            Some(limit) if limit <= 0 || limit > MAX_LIMIT => Err(Error::Params),
+            Some(limit) => Ok(limit),
+            None => Ok(DEFAULT_LIMIT),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub offset: i64,
+    pub limit: i64,
+}
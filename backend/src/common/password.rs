@@ -0,0 +1,21 @@
+use argon2rs::verifier::Encoded;
+use argon2rs::{Argon2, Variant};
+use rand::Rng;
+
+const SALT_LEN: usize = 16;
+
+pub fn hash(password: &str) -> String {
+    let mut rng = rand::thread_rng();
+    let salt: Vec<u8> = (0..SALT_LEN).map(|_| rng.gen()).collect();
+    let argon2 = Argon2::default(Variant::Argon2i);
+    let encoded = Encoded::new(argon2, password.as_bytes(), &salt, b"", b"");
+
+    String::from_utf8(encoded.to_u8()).expect("argon2 encoding is valid utf8")
+}
+
+pub fn verify(password: &str, encoded: &str) -> bool {
+    match Encoded::from_u8(encoded.as_bytes()) {
+        Ok(encoded) => encoded.verify(password.as_bytes()),
+        Err(_) => false,
+    }
+}
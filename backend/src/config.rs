@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+
+use dotenv::dotenv;
+use toml;
+
+const DEFAULT_WINDOW_SECS: i64 = 60;
+const DEFAULT_LIMIT: u64 = 120;
+const DEFAULT_FAIL_THRESHOLD: u32 = 5;
+const DEFAULT_BASE_LOCKOUT_SECS: i64 = 30;
+const DEFAULT_MAX_LOCKOUT_SECS: i64 = 60 * 60;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct JwtConfig {
+    pub secret: String,
+    pub expires_in: i64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PostgresConfig {
+    pub addr: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RedisConfig {
+    pub addr: String,
+}
+
+/// Per route-group request quotas enforced by `fairings::ratelimit`. Any
+/// group not listed in `groups` falls back to `default_limit`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct RateLimitConfig {
+    #[serde(default = "default_window_secs")]
+    pub window_secs: i64,
+    #[serde(default = "default_limit")]
+    pub default_limit: u64,
+    #[serde(default)]
+    pub groups: HashMap<String, u64>,
+}
+
+fn default_window_secs() -> i64 {
+    DEFAULT_WINDOW_SECS
+}
+
+fn default_limit() -> u64 {
+    DEFAULT_LIMIT
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> RateLimitConfig {
+        RateLimitConfig {
+            window_secs: DEFAULT_WINDOW_SECS,
+            default_limit: DEFAULT_LIMIT,
+            groups: HashMap::new(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn limit_for(&self, group: &str) -> u64 {
+        self.groups
+            .get(group)
+            .cloned()
+            .unwrap_or(self.default_limit)
+    }
+}
+
+/// Account lockout / failed-signin throttling, enforced by `handlers::user`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct LockoutConfig {
+    #[serde(default = "default_fail_threshold")]
+    pub fail_threshold: u32,
+    #[serde(default = "default_base_lockout_secs")]
+    pub base_lockout_secs: i64,
+    #[serde(default = "default_max_lockout_secs")]
+    pub max_lockout_secs: i64,
+}
+
+fn default_fail_threshold() -> u32 {
+    DEFAULT_FAIL_THRESHOLD
+}
+
+fn default_base_lockout_secs() -> i64 {
+    DEFAULT_BASE_LOCKOUT_SECS
+}
+
+fn default_max_lockout_secs() -> i64 {
+    DEFAULT_MAX_LOCKOUT_SECS
+}
+
+impl Default for LockoutConfig {
+    fn default() -> LockoutConfig {
+        LockoutConfig {
+            fail_threshold: DEFAULT_FAIL_THRESHOLD,
+            base_lockout_secs: DEFAULT_BASE_LOCKOUT_SECS,
+            max_lockout_secs: DEFAULT_MAX_LOCKOUT_SECS,
+        }
+    }
+}
+
+impl LockoutConfig {
+    /// Exponential backoff once `attempts` crosses `fail_threshold`, capped
+    /// at `max_lockout_secs`.
+    pub fn lockout_duration(&self, attempts: u32) -> i64 {
+        let exponent = attempts.saturating_sub(self.fail_threshold);
+        let secs = self
+            .base_lockout_secs
+            .saturating_mul(2i64.saturating_pow(exponent));
+
+        secs.min(self.max_lockout_secs)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Config {
+    pub jwt: JwtConfig,
+    pub postgres: PostgresConfig,
+    pub redis: RedisConfig,
+    #[serde(default)]
+    pub ratelimit: RateLimitConfig,
+    #[serde(default)]
+    pub lockout: LockoutConfig,
+}
+
+pub fn parse() -> Config {
+    dotenv().ok();
+
+    let path = env::var("CONFIG_PATH").unwrap_or_else(|_| String::from("Config.toml"));
+    let content = fs::read_to_string(&path).expect("failed to read config file");
+
+    toml::from_str(&content).expect("failed to parse config file")
+}
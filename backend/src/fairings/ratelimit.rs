@@ -0,0 +1,132 @@
+use chrono::Utc;
+use redis::Commands;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Method;
+use rocket::{Data, Request, Response, State};
+
+use super::super::config::Config;
+use super::super::guards::Ticket;
+use super::super::storage::Cache;
+use super::super::handlers::Error;
+
+/// Sentinel route `on_request` reroutes to once a bucket is exhausted, so the
+/// real handler (and its DB/Redis work) never runs for a throttled request.
+const THROTTLED_URI: &str = "/__ratelimit/throttled";
+
+/// Result of the request-side counter check, stashed via `local_cache` so
+/// `on_response` can render it without talking to Redis a second time.
+struct Outcome {
+    limit: u64,
+    remaining: i64,
+    retry_after: u64,
+}
+
+#[get("/__ratelimit/throttled")]
+pub fn throttled(request: &Request) -> Error {
+    let outcome = request.local_cache(|| Outcome {
+        limit: 0,
+        remaining: 0,
+        retry_after: 1,
+    });
+
+    Error::RateLimited {
+        retry_after: outcome.retry_after,
+    }
+}
+
+fn client_key(request: &Request) -> String {
+    match request.guard::<Ticket>() {
+        rocket::Outcome::Success(ticket) => format!("user:{}", ticket.user_id),
+        _ => {
+            let ip = request
+                .client_ip()
+                .map(|ip| ip.to_string())
+                .unwrap_or_else(|| String::from("unknown"));
+            format!("ip:{}", ip)
+        }
+    }
+}
+
+fn group_for(request: &Request) -> &'static str {
+    let path = request.uri().path();
+    if path.starts_with("/api/v1/oauth") || path.starts_with("/api/v1/ticket") {
+        "auth"
+    } else {
+        "default"
+    }
+}
+
+/// Redis-backed fixed-window limiter. One counter per route group, client
+/// key and configured window; the counter is a plain `INCR`/`EXPIRE` pair
+/// rather than a Lua script, which is good enough at our traffic and keeps
+/// the fairing dependency-free.
+pub struct RateLimiter;
+
+impl Fairing for RateLimiter {
+    fn info(&self) -> Info {
+        Info {
+            name: "Rate Limiter",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    fn on_request(&self, request: &mut Request, _data: &Data) {
+        let cache = match request.guard::<State<Cache>>() {
+            rocket::Outcome::Success(cache) => cache,
+            _ => return,
+        };
+        let config = match request.guard::<State<Config>>() {
+            rocket::Outcome::Success(config) => config,
+            _ => return,
+        };
+
+        let group = group_for(request);
+        let limit = config.ratelimit.limit_for(group);
+        let window = config.ratelimit.window_secs;
+        let bucket = Utc::now().timestamp() / window;
+        let key = format!("ratelimit:{}:{}:{}", group, client_key(request), bucket);
+
+        let conn = match cache.get_conn() {
+            Ok(conn) => conn,
+            Err(_) => return,
+        };
+
+        let count: i64 = match conn.incr(key.as_str(), 1) {
+            Ok(count) => count,
+            Err(_) => return,
+        };
+        if count == 1 {
+            let _: Result<(), _> = conn.expire(key.as_str(), window as usize);
+        }
+
+        let remaining = limit as i64 - count;
+        request.local_cache(|| Outcome {
+            limit,
+            remaining,
+            retry_after: window as u64,
+        });
+
+        if remaining < 0 {
+            request.set_method(Method::Get);
+            request.set_uri(THROTTLED_URI);
+        }
+    }
+
+    fn on_response(&self, request: &Request, response: &mut Response) {
+        let outcome = request.local_cache(|| Outcome {
+            limit: 0,
+            remaining: 0,
+            retry_after: 0,
+        });
+
+        if outcome.limit == 0 {
+            return;
+        }
+
+        response.set_raw_header("X-RateLimit-Limit", outcome.limit.to_string());
+        response.set_raw_header(
+            "X-RateLimit-Remaining",
+            outcome.remaining.max(0).to_string(),
+        );
+    }
+}
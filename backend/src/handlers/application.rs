@@ -0,0 +1,29 @@
+use rocket::State;
+use rocket_contrib::Json;
+
+use super::super::common::pagination::{Page, Pagination};
+use super::super::guards::Ticket;
+use super::super::models::application;
+use super::super::models::application::Application;
+use super::super::storage::Database;
+use super::Error;
+
+#[get("/applications?<pagination>")]
+fn select_applications(
+    pagination: Pagination,
+    ticket: Ticket,
+    db: State<Database>,
+) -> Result<Json<Page<Application>>, Error> {
+    let offset = pagination.offset()?;
+    let limit = pagination.limit()?;
+    let conn = db.get_conn()?;
+    let applications = application::select(&*conn, ticket.user_id, offset, limit)?;
+    let total = application::count(&*conn, ticket.user_id)?;
+
+    Ok(Json(Page {
+        items: applications,
+        total,
+        offset,
+        limit,
+    }))
+}
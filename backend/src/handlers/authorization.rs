@@ -0,0 +1,52 @@
+use rocket::State;
+use rocket_contrib::Json;
+
+use super::super::common::pagination::{Page, Pagination};
+use super::super::guards::Ticket;
+use super::super::models::authorization;
+use super::super::models::authorization::Authorization;
+use super::super::storage::{Cache, Database};
+use super::token;
+use super::Error;
+
+#[get("/authorizations?<pagination>")]
+fn select_authorizations(
+    pagination: Pagination,
+    ticket: Ticket,
+    db: State<Database>,
+) -> Result<Json<Page<Authorization>>, Error> {
+    let offset = pagination.offset()?;
+    let limit = pagination.limit()?;
+    let conn = db.get_conn()?;
+    let authorizations = authorization::select(&*conn, ticket.user_id, offset, limit)?;
+    let total = authorization::count(&*conn, ticket.user_id)?;
+
+    Ok(Json(Page {
+        items: authorizations,
+        total,
+        offset,
+        limit,
+    }))
+}
+
+#[delete("/authorizations/<id>")]
+fn remove_authorization(
+    id: i64,
+    ticket: Ticket,
+    db: State<Database>,
+    cache: State<Cache>,
+) -> Result<Json<Authorization>, Error> {
+    let conn = db.get_conn()?;
+    let existing = authorization::select_one(&*conn, id)?;
+    if existing.user_id != ticket.user_id {
+        return Err(Error::Forbidden);
+    }
+
+    let removed = authorization::remove(&*conn, id)?;
+
+    // Revoking the authorization must also invalidate any refresh tokens
+    // minted under it, otherwise a stolen one keeps working past revocation.
+    token::revoke_refresh_tokens(&cache, id)?;
+
+    Ok(Json(removed))
+}
@@ -0,0 +1,67 @@
+use rocket::State;
+use rocket_contrib::Json;
+use validator::{validate_email, Validate, ValidationError, ValidationErrors};
+
+use super::super::common::pagination::{Page, Pagination};
+use super::super::guards::Ticket;
+use super::super::models::contact;
+use super::super::models::contact::Contact;
+use super::super::storage::Database;
+use super::Error;
+
+#[derive(Deserialize, Validate, Debug)]
+pub struct CreateContactRequest {
+    #[validate(length(min = "1", max = "32"))]
+    kind: String,
+    #[validate(length(min = "1", max = "256"))]
+    value: String,
+}
+
+/// Checks `value` against the format its `kind` implies. Reports under the
+/// `value` field rather than as a struct-level `__all__` error, since it's
+/// `value` the caller needs to correct.
+fn validate_contact(request: &CreateContactRequest) -> Result<(), ValidationErrors> {
+    if request.kind == "email" && !validate_email(&request.value) {
+        let mut errors = ValidationErrors::new();
+        errors.add("value", ValidationError::new("invalid_email"));
+        return Err(errors);
+    }
+
+    Ok(())
+}
+
+#[post("/contacts", format = "application/json", data = "<request>")]
+fn create_contact(
+    request: Json<CreateContactRequest>,
+    ticket: Ticket,
+    db: State<Database>,
+) -> Result<Json<Contact>, Error> {
+    let request = request.into_inner();
+    request.validate()?;
+    validate_contact(&request)?;
+
+    let conn = db.get_conn()?;
+    let created = contact::create(&*conn, ticket.user_id, &request.kind, &request.value)?;
+
+    Ok(Json(created))
+}
+
+#[get("/contacts?<pagination>")]
+fn select_contacts(
+    pagination: Pagination,
+    ticket: Ticket,
+    db: State<Database>,
+) -> Result<Json<Page<Contact>>, Error> {
+    let offset = pagination.offset()?;
+    let limit = pagination.limit()?;
+    let conn = db.get_conn()?;
+    let contacts = contact::select(&*conn, ticket.user_id, offset, limit)?;
+    let total = contact::count(&*conn, ticket.user_id)?;
+
+    Ok(Json(Page {
+        items: contacts,
+        total,
+        offset,
+        limit,
+    }))
+}
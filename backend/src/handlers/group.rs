@@ -1,15 +1,27 @@
 use rocket::State;
 use rocket_contrib::Json;
 
+use super::super::common::pagination::{Page, Pagination};
 use super::super::models::group;
 use super::super::models::group::Group;
 use super::super::storage::Database;
 use super::Error;
 
-#[get("/groups")]
-fn select_groups(db: State<Database>) -> Result<Json<Vec<Group>>, Error> {
+#[get("/groups?<pagination>")]
+fn select_groups(
+    pagination: Pagination,
+    db: State<Database>,
+) -> Result<Json<Page<Group>>, Error> {
+    let offset = pagination.offset()?;
+    let limit = pagination.limit()?;
     let conn = db.get_conn()?;
-    let groups = group::select(&*conn)?;
+    let groups = group::select(&*conn, offset, limit)?;
+    let total = group::count(&*conn)?;
 
-    Ok(Json(groups))
+    Ok(Json(Page {
+        items: groups,
+        total,
+        offset,
+        limit,
+    }))
 }
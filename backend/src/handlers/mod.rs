@@ -18,6 +18,7 @@ use postgres::error::UNIQUE_VIOLATION;
 use r2d2::Error as R2d2Error;
 use redis::RedisError;
 use url::ParseError;
+use validator::ValidationErrors;
 
 pub mod application;
 pub mod authorization;
@@ -47,6 +48,20 @@ pub enum Error {
     Params,
     Privilege,
     Forbidden,
+    InvalidGrant,
+    RateLimited { retry_after: u64 },
+    AccountLocked { retry_after: u64 },
+    InvalidRecoveryCode,
+    InvalidChallenge,
+    TotpNotEnabled,
+    Validation(Vec<FieldError>),
+}
+
+#[derive(Serialize, Debug)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
 }
 
 impl fmt::Display for Error {
@@ -62,6 +77,13 @@ impl fmt::Display for Error {
             Error::Params => write!(f, "Params"),
             Error::Forbidden => write!(f, "Forbidden"),
             Error::Privilege => write!(f, "Privilege"),
+            Error::InvalidGrant => write!(f, "InvalidGrant"),
+            Error::RateLimited { .. } => write!(f, "RateLimited"),
+            Error::AccountLocked { .. } => write!(f, "AccountLocked"),
+            Error::InvalidRecoveryCode => write!(f, "InvalidRecoveryCode"),
+            Error::InvalidChallenge => write!(f, "InvalidChallenge"),
+            Error::TotpNotEnabled => write!(f, "TotpNotEnabled"),
+            Error::Validation(ref errors) => write!(f, "Validation({} field(s))", errors.len()),
         }
     }
 }
@@ -79,6 +101,13 @@ impl StdError for Error {
             Error::Params => "Params",
             Error::Privilege => "Privilege",
             Error::Forbidden => "Forbidden",
+            Error::InvalidGrant => "InvalidGrant",
+            Error::RateLimited { .. } => "RateLimited",
+            Error::AccountLocked { .. } => "AccountLocked",
+            Error::InvalidRecoveryCode => "InvalidRecoveryCode",
+            Error::InvalidChallenge => "InvalidChallenge",
+            Error::TotpNotEnabled => "TotpNotEnabled",
+            Error::Validation(_) => "Validation",
         }
     }
 }
@@ -131,10 +160,64 @@ impl From<FromUtf8Error> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(_err: serde_json::Error) -> Error {
+        Error::Params
+    }
+}
+
+impl From<ValidationErrors> for Error {
+    fn from(errors: ValidationErrors) -> Error {
+        let field_errors = errors
+            .field_errors()
+            .into_iter()
+            .flat_map(|(field, errors)| {
+                errors.into_iter().map(move |err| FieldError {
+                    field: field.to_string(),
+                    code: err.code.to_string(),
+                    message: err
+                        .message
+                        .clone()
+                        .map(|message| message.to_string())
+                        .unwrap_or_else(|| format!("{} is invalid", field)),
+                })
+            })
+            .collect();
+
+        Error::Validation(field_errors)
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct ValidationBody<'a> {
+    errno: &'a str,
+    errmsg: &'a str,
+    errors: &'a [FieldError],
+}
+
 impl<'r> Responder<'r> for Error {
     fn respond_to(self, _req: &Request) -> Result<Response<'r>, HttpStatus> {
         println!("handler error: {}", self);
+
+        if let Error::Validation(ref errors) = self {
+            let payload = ValidationBody {
+                errno: "40000020",
+                errmsg: "validation failed",
+                errors,
+            };
+
+            return match serde_json::to_string(&payload) {
+                Ok(body) => Ok(Response::build()
+                    .status(HttpStatus::BadRequest)
+                    .header(ContentType::JSON)
+                    .sized_body(Cursor::new(body))
+                    .finalize()),
+                Err(_) => Err(HttpStatus::InternalServerError),
+            };
+        }
+
         let mut body = HashMap::new();
+        let mut retry_after = None;
 
         let status = match self {
             Error::IO(ref _io_err) => {
@@ -226,16 +309,63 @@ impl<'r> Responder<'r> for Error {
 
                 HttpStatus::Unauthorized
             }
+            Error::InvalidGrant => {
+                body.insert("errno", "40000010");
+                body.insert("errmsg", "invalid or expired grant");
+
+                HttpStatus::BadRequest
+            }
+            Error::RateLimited {
+                retry_after: after,
+            } => {
+                retry_after = Some(after);
+                body.insert("errno", "42900001");
+                body.insert("errmsg", "too many requests");
+
+                HttpStatus::TooManyRequests
+            }
+            Error::AccountLocked {
+                retry_after: after,
+            } => {
+                retry_after = Some(after);
+                body.insert("errno", "42300002");
+                body.insert("errmsg", "account temporarily locked");
+
+                HttpStatus::Forbidden
+            }
+            Error::Validation(_) => unreachable!("handled above before the match"),
+            Error::InvalidRecoveryCode => {
+                body.insert("errno", "40100001");
+                body.insert("errmsg", "invalid or exhausted recovery code");
+
+                HttpStatus::Unauthorized
+            }
+            Error::InvalidChallenge => {
+                body.insert("errno", "40100002");
+                body.insert("errmsg", "invalid or expired signin challenge");
+
+                HttpStatus::Unauthorized
+            }
+            Error::TotpNotEnabled => {
+                body.insert("errno", "40000030");
+                body.insert("errmsg", "totp is not enabled for this account");
+
+                HttpStatus::BadRequest
+            }
         };
 
         match serde_json::to_string(&body) {
             Ok(body) => {
-                let response = Response::build()
+                let mut builder = Response::build();
+                builder
                     .status(status)
                     .header(ContentType::JSON)
-                    .sized_body(Cursor::new(body))
-                    .finalize();
-                Ok(response)
+                    .sized_body(Cursor::new(body));
+                if let Some(retry_after) = retry_after {
+                    builder.raw_header("Retry-After", retry_after.to_string());
+                }
+
+                Ok(builder.finalize())
             }
             Err(_) => Err(HttpStatus::InternalServerError),
         }
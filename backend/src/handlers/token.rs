@@ -0,0 +1,230 @@
+use chrono::Utc;
+use rand::Rng;
+use rocket::State;
+use rocket_contrib::Json;
+
+use hex;
+use jwt;
+use jwt::Header;
+use redis;
+use redis::Commands;
+use serde_json;
+
+use super::super::config::Config;
+use super::super::storage::Cache;
+use super::Error;
+
+const REFRESH_TOKEN_BYTES: usize = 32;
+const REFRESH_TOKEN_TTL_SECS: usize = 60 * 60 * 24 * 30;
+
+#[derive(Deserialize, Debug)]
+pub struct TokenRequest {
+    grant_type: String,
+    code: Option<String>,
+    refresh_token: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TokenResponse {
+    access_token: String,
+    token_type: String,
+    expires_in: i64,
+    refresh_token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Claims {
+    sub: String,
+    aud: String,
+    scopes: Vec<String>,
+    exp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct CodeGrant {
+    user_id: String,
+    application_id: String,
+    authorization_id: i64,
+    scopes: Vec<String>,
+}
+
+fn code_key(code: &str) -> String {
+    format!("ticket:{}", code)
+}
+
+/// Redeems an authorization code minted by `handlers::ticket`. Codes are
+/// single-use: `DEL` is the atomic claim, so even if two callers both read
+/// the code in a race, only the `DEL` that actually removed the key — its
+/// return count is 1, not 0 — is allowed to use the value it read.
+fn redeem_code(cache: &Cache, code: &str) -> Result<CodeGrant, Error> {
+    let conn = cache.get_conn()?;
+    let value: Option<String> = conn.get(code_key(code))?;
+    let value = value.ok_or(Error::InvalidGrant)?;
+
+    let claimed: i64 = conn.del(code_key(code))?;
+    if claimed == 0 {
+        return Err(Error::InvalidGrant);
+    }
+
+    serde_json::from_str(&value).map_err(|_| Error::InvalidGrant)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct RefreshRecord {
+    user_id: String,
+    application_id: String,
+    authorization_id: i64,
+    scopes: Vec<String>,
+    issued_at: i64,
+}
+
+fn refresh_key(token: &str) -> String {
+    format!("refresh:{}", token)
+}
+
+fn refresh_index_key(authorization_id: i64) -> String {
+    format!("refresh_index:{}", authorization_id)
+}
+
+fn generate_refresh_token() -> String {
+    let mut rng = rand::thread_rng();
+    let bytes: Vec<u8> = (0..REFRESH_TOKEN_BYTES).map(|_| rng.gen()).collect();
+    hex::encode(bytes)
+}
+
+fn mint_access_token(
+    config: &Config,
+    user_id: &str,
+    application_id: &str,
+    scopes: &[String],
+) -> Result<(String, i64), Error> {
+    let expires_in = config.jwt.expires_in;
+    let claims = Claims {
+        sub: user_id.to_string(),
+        aud: application_id.to_string(),
+        scopes: scopes.to_vec(),
+        exp: Utc::now().timestamp() + expires_in,
+    };
+    let access_token = jwt::encode(&Header::default(), &claims, config.jwt.secret.as_ref())?;
+
+    Ok((access_token, expires_in))
+}
+
+fn issue_refresh_token(
+    cache: &Cache,
+    user_id: &str,
+    application_id: &str,
+    authorization_id: i64,
+    scopes: &[String],
+) -> Result<String, Error> {
+    let conn = cache.get_conn()?;
+    let token = generate_refresh_token();
+    let record = RefreshRecord {
+        user_id: user_id.to_string(),
+        application_id: application_id.to_string(),
+        authorization_id,
+        scopes: scopes.to_vec(),
+        issued_at: Utc::now().timestamp(),
+    };
+    let value = serde_json::to_string(&record)?;
+
+    conn.set_ex::<_, _, ()>(refresh_key(&token), value, REFRESH_TOKEN_TTL_SECS)?;
+    conn.sadd::<_, _, ()>(refresh_index_key(authorization_id), &token)?;
+
+    Ok(token)
+}
+
+/// Deletes a single refresh token and its index entry. `DEL` is the atomic
+/// single-use claim: returns whether this call is the one that actually
+/// removed the token, so a caller can tell a genuine revocation apart from
+/// a race lost to a concurrent rotation.
+fn revoke_refresh_token(conn: &redis::Connection, token: &str, authorization_id: i64) -> Result<bool, Error> {
+    let claimed: i64 = conn.del(refresh_key(token))?;
+    conn.srem::<_, _, ()>(refresh_index_key(authorization_id), token)?;
+
+    Ok(claimed > 0)
+}
+
+/// Deletes every refresh token issued under `authorization_id`. Called when an
+/// authorization is revoked so a leaked refresh token can't outlive its grant.
+pub fn revoke_refresh_tokens(cache: &Cache, authorization_id: i64) -> Result<(), Error> {
+    let conn = cache.get_conn()?;
+    let tokens: Vec<String> = conn.smembers(refresh_index_key(authorization_id))?;
+    for token in &tokens {
+        conn.del::<_, ()>(refresh_key(token))?;
+    }
+    conn.del::<_, ()>(refresh_index_key(authorization_id))?;
+
+    Ok(())
+}
+
+#[post("/oauth/token", format = "application/json", data = "<request>")]
+fn create_token(
+    request: Json<TokenRequest>,
+    config: State<Config>,
+    cache: State<Cache>,
+) -> Result<Json<TokenResponse>, Error> {
+    let request = request.into_inner();
+
+    match request.grant_type.as_str() {
+        "authorization_code" => {
+            let code = request.code.ok_or(Error::InvalidGrant)?;
+            let grant = redeem_code(&cache, &code)?;
+
+            let (access_token, expires_in) =
+                mint_access_token(&config, &grant.user_id, &grant.application_id, &grant.scopes)?;
+            let refresh_token = issue_refresh_token(
+                &cache,
+                &grant.user_id,
+                &grant.application_id,
+                grant.authorization_id,
+                &grant.scopes,
+            )?;
+
+            Ok(Json(TokenResponse {
+                access_token,
+                token_type: String::from("bearer"),
+                expires_in,
+                refresh_token,
+            }))
+        }
+        "refresh_token" => {
+            let presented = request.refresh_token.ok_or(Error::InvalidGrant)?;
+            let conn = cache.get_conn()?;
+            let value: Option<String> = conn.get(refresh_key(&presented))?;
+            let value = value.ok_or(Error::InvalidGrant)?;
+            let record: RefreshRecord = serde_json::from_str(&value).map_err(|_| Error::InvalidGrant)?;
+
+            // Rotate: the presented token is single-use, whether or not it was leaked.
+            // `revoke_refresh_token`'s DEL is the atomic claim — a concurrent exchange
+            // that loses the race sees `claimed == false` and is rejected here instead
+            // of also minting a token pair.
+            let claimed = revoke_refresh_token(&conn, &presented, record.authorization_id)?;
+            if !claimed {
+                return Err(Error::InvalidGrant);
+            }
+
+            let (access_token, expires_in) = mint_access_token(
+                &config,
+                &record.user_id,
+                &record.application_id,
+                &record.scopes,
+            )?;
+            let refresh_token = issue_refresh_token(
+                &cache,
+                &record.user_id,
+                &record.application_id,
+                record.authorization_id,
+                &record.scopes,
+            )?;
+
+            Ok(Json(TokenResponse {
+                access_token,
+                token_type: String::from("bearer"),
+                expires_in,
+                refresh_token,
+            }))
+        }
+        _ => Err(Error::InvalidGrant),
+    }
+}
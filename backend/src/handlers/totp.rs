@@ -0,0 +1,161 @@
+use rand::Rng;
+use redis::Commands;
+use rocket::State;
+use rocket_contrib::Json;
+
+use super::super::common::password;
+use super::super::guards::Ticket;
+use super::super::models::totp_recovery_code;
+use super::super::models::user as user_model;
+use super::super::models::user::User;
+use super::super::storage::{Cache, Database};
+use super::Error;
+
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_LEN: usize = 10;
+const RECOVERY_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Recovery-code verification attempts allowed per signin challenge before
+/// it's burned outright, forcing a fresh signin.
+const MAX_VERIFY_ATTEMPTS: u32 = 5;
+
+#[derive(Serialize, Debug)]
+pub struct RecoveryCodes {
+    codes: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct VerifyRecoveryCodeRequest {
+    challenge: String,
+    code: String,
+}
+
+fn generate_codes() -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            (0..RECOVERY_CODE_LEN)
+                .map(|_| {
+                    let idx = rng.gen_range(0, RECOVERY_CODE_ALPHABET.len());
+                    RECOVERY_CODE_ALPHABET[idx] as char
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn store_codes(db: &Database, user_id: i64, codes: &[String]) -> Result<(), Error> {
+    let conn = db.get_conn()?;
+    let hashes: Vec<String> = codes.iter().map(|code| password::hash(code)).collect();
+    totp_recovery_code::insert_many(&*conn, user_id, &hashes)?;
+
+    Ok(())
+}
+
+fn ensure_totp_enabled(db: &Database, user_id: i64) -> Result<(), Error> {
+    let conn = db.get_conn()?;
+    let account = user_model::select_by_id(&*conn, user_id)?;
+    if !account.totp_enabled {
+        return Err(Error::TotpNotEnabled);
+    }
+
+    Ok(())
+}
+
+/// Invalidates any prior batch and mints a fresh one, so only one set of 10
+/// codes is ever live at a time.
+fn issue_recovery_codes(db: &Database, user_id: i64) -> Result<Vec<String>, Error> {
+    ensure_totp_enabled(db, user_id)?;
+
+    {
+        let conn = db.get_conn()?;
+        totp_recovery_code::invalidate_all(&*conn, user_id)?;
+    }
+
+    let codes = generate_codes();
+    store_codes(db, user_id, &codes)?;
+
+    Ok(codes)
+}
+
+/// Mints a fresh batch of recovery codes and returns them in the clear, this
+/// once — only their argon2 hashes are persisted. Any unconsumed codes from
+/// a prior batch are invalidated first, so repeated calls can't accumulate
+/// multiple live batches.
+#[post("/totp/recovery-codes")]
+fn generate_recovery_codes(ticket: Ticket, db: State<Database>) -> Result<Json<RecoveryCodes>, Error> {
+    let codes = issue_recovery_codes(&db, ticket.user_id)?;
+
+    Ok(Json(RecoveryCodes { codes }))
+}
+
+/// Invalidates every prior code before minting a new batch, so a previously
+/// displayed (and possibly exposed) set stops working.
+#[put("/totp/recovery-codes")]
+fn regenerate_recovery_codes(ticket: Ticket, db: State<Database>) -> Result<Json<RecoveryCodes>, Error> {
+    let codes = issue_recovery_codes(&db, ticket.user_id)?;
+
+    Ok(Json(RecoveryCodes { codes }))
+}
+
+/// Consumes one recovery code in place of a TOTP, e.g. mid-signin. Matches
+/// against every unconsumed hash for the user and marks the first match
+/// consumed so it can never be redeemed again.
+pub fn consume_recovery_code(db: &Database, user_id: i64, code: &str) -> Result<(), Error> {
+    let conn = db.get_conn()?;
+    let unconsumed = totp_recovery_code::select_unconsumed(&*conn, user_id)?;
+
+    let matched = unconsumed
+        .iter()
+        .find(|entry| password::verify(code, &entry.code_hash));
+
+    match matched {
+        Some(entry) => {
+            totp_recovery_code::consume(&*conn, entry.id)?;
+            Ok(())
+        }
+        None => Err(Error::InvalidRecoveryCode),
+    }
+}
+
+fn verify_attempts_key(challenge: &str) -> String {
+    format!("totp:recovery:attempts:{}", challenge)
+}
+
+/// Throttles recovery-code guesses per signin challenge rather than per
+/// user, since the challenge (not a caller-supplied user id) is the only
+/// thing authenticating the attempt.
+fn record_verify_attempt(cache: &Cache, challenge: &str) -> Result<u32, Error> {
+    let conn = cache.get_conn()?;
+    let attempts: u32 = conn.incr(verify_attempts_key(challenge), 1)?;
+    conn.expire::<_, ()>(verify_attempts_key(challenge), super::user::SIGNIN_CHALLENGE_TTL_SECS)?;
+
+    Ok(attempts)
+}
+
+/// Resolves a pending signin challenge with a recovery code instead of a
+/// TOTP. The challenge — never an attacker-supplied `user_id` — is the sole
+/// source of the target account, and attempts against it are throttled.
+#[post("/totp/recovery-codes/verify", format = "application/json", data = "<request>")]
+fn verify_recovery_code(
+    request: Json<VerifyRecoveryCodeRequest>,
+    db: State<Database>,
+    cache: State<Cache>,
+) -> Result<Json<User>, Error> {
+    let request = request.into_inner();
+
+    let attempts = record_verify_attempt(&cache, &request.challenge)?;
+    if attempts > MAX_VERIFY_ATTEMPTS {
+        super::user::revoke_signin_challenge(&cache, &request.challenge)?;
+        return Err(Error::InvalidChallenge);
+    }
+
+    let user_id = super::user::resolve_signin_challenge(&cache, &request.challenge)?;
+    consume_recovery_code(&db, user_id, &request.code)?;
+    super::user::revoke_signin_challenge(&cache, &request.challenge)?;
+
+    let conn = db.get_conn()?;
+    let account = user_model::select_by_id(&*conn, user_id)?;
+
+    Ok(Json(account))
+}
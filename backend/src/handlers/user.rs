@@ -0,0 +1,161 @@
+use chrono::{Duration, Utc};
+use hex;
+use rand::Rng;
+use redis::Commands;
+use rocket::State;
+use rocket_contrib::Json;
+use validator::Validate;
+
+use super::super::common::password;
+use super::super::config::Config;
+use super::super::models::user;
+use super::super::models::user::User;
+use super::super::storage::{Cache, Database};
+use super::Error;
+
+const SIGNIN_CHALLENGE_BYTES: usize = 16;
+/// How long a caller has to complete a second factor after a password check
+/// succeeds on a TOTP-enabled account. Also used by `handlers::totp` to
+/// bound its per-challenge attempt counter to the challenge's own lifetime.
+pub const SIGNIN_CHALLENGE_TTL_SECS: usize = 5 * 60;
+
+#[derive(Deserialize, Validate, Debug)]
+pub struct SignupRequest {
+    #[validate(length(min = "3", max = "32"), non_control_character)]
+    username: String,
+    #[validate(length(min = "8", max = "128"))]
+    password: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SigninRequest {
+    username: String,
+    password: String,
+}
+
+/// Either the caller is fully signed in, or the account requires a second
+/// factor and must follow up with the returned `challenge` (e.g. against
+/// `handlers::totp::verify_recovery_code`).
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum SigninResponse {
+    Authenticated(User),
+    ChallengeRequired { challenge: String },
+}
+
+fn fail_key(user_id: i64) -> String {
+    format!("signin:fail:{}", user_id)
+}
+
+/// `attempts`' counter expires `max_lockout_secs` after the latest failure,
+/// the longest lockout window any account can earn, so a stale counter can't
+/// outlive its purpose.
+fn record_failure(cache: &Cache, config: &Config, user_id: i64) -> Result<u32, Error> {
+    let conn = cache.get_conn()?;
+    let attempts: u32 = conn.incr(fail_key(user_id), 1)?;
+    conn.expire::<_, ()>(fail_key(user_id), config.lockout.max_lockout_secs as usize)?;
+
+    Ok(attempts)
+}
+
+fn clear_failures(cache: &Cache, user_id: i64) -> Result<(), Error> {
+    let conn = cache.get_conn()?;
+    conn.del::<_, ()>(fail_key(user_id))?;
+
+    Ok(())
+}
+
+fn challenge_key(challenge: &str) -> String {
+    format!("signin:challenge:{}", challenge)
+}
+
+fn generate_challenge() -> String {
+    let mut rng = rand::thread_rng();
+    let bytes: Vec<u8> = (0..SIGNIN_CHALLENGE_BYTES).map(|_| rng.gen()).collect();
+    hex::encode(bytes)
+}
+
+/// Issues a single-use signin challenge bound to `user_id`, standing in for
+/// the account until a second factor is presented.
+fn issue_signin_challenge(cache: &Cache, user_id: i64) -> Result<String, Error> {
+    let conn = cache.get_conn()?;
+    let challenge = generate_challenge();
+    conn.set_ex::<_, _, ()>(challenge_key(&challenge), user_id, SIGNIN_CHALLENGE_TTL_SECS)?;
+
+    Ok(challenge)
+}
+
+/// Resolves a signin challenge to the `user_id` it was issued for. Never
+/// trust a caller-supplied `user_id` in its place — the challenge is the
+/// only proof that a password check already succeeded.
+pub fn resolve_signin_challenge(cache: &Cache, challenge: &str) -> Result<i64, Error> {
+    let conn = cache.get_conn()?;
+    let user_id: Option<i64> = conn.get(challenge_key(challenge))?;
+
+    user_id.ok_or(Error::InvalidChallenge)
+}
+
+/// Burns a signin challenge so it can't be replayed, whether it was
+/// consumed successfully or is being discarded after too many bad attempts.
+pub fn revoke_signin_challenge(cache: &Cache, challenge: &str) -> Result<(), Error> {
+    let conn = cache.get_conn()?;
+    conn.del::<_, ()>(challenge_key(challenge))?;
+
+    Ok(())
+}
+
+#[post("/signup", format = "application/json", data = "<request>")]
+fn signup(request: Json<SignupRequest>, db: State<Database>) -> Result<Json<User>, Error> {
+    let request = request.into_inner();
+    request.validate()?;
+
+    let conn = db.get_conn()?;
+    let password_hash = password::hash(&request.password);
+    let account = user::create(&*conn, &request.username, &password_hash)?;
+
+    Ok(Json(account))
+}
+
+#[post("/signin", format = "application/json", data = "<request>")]
+fn signin(
+    request: Json<SigninRequest>,
+    db: State<Database>,
+    cache: State<Cache>,
+    config: State<Config>,
+) -> Result<Json<SigninResponse>, Error> {
+    let request = request.into_inner();
+    let conn = db.get_conn()?;
+    let account = user::select_by_username(&*conn, &request.username)?;
+
+    if let Some(locked_until) = account.locked_until {
+        if locked_until > Utc::now() {
+            let retry_after = (locked_until - Utc::now()).num_seconds().max(0) as u64;
+            return Err(Error::AccountLocked { retry_after });
+        }
+    }
+
+    if !password::verify(&request.password, &account.password_hash) {
+        let attempts = record_failure(&cache, &config, account.id)?;
+        if attempts >= config.lockout.fail_threshold {
+            let retry_after = config.lockout.lockout_duration(attempts);
+            let locked_until = Utc::now() + Duration::seconds(retry_after);
+            user::set_locked_until(&*conn, account.id, Some(locked_until))?;
+
+            return Err(Error::AccountLocked {
+                retry_after: retry_after as u64,
+            });
+        }
+
+        return Err(Error::Forbidden);
+    }
+
+    clear_failures(&cache, account.id)?;
+    user::set_locked_until(&*conn, account.id, None)?;
+
+    if account.totp_enabled {
+        let challenge = issue_signin_challenge(&cache, account.id)?;
+        return Ok(Json(SigninResponse::ChallengeRequired { challenge }));
+    }
+
+    Ok(Json(SigninResponse::Authenticated(account)))
+}
@@ -24,6 +24,9 @@ extern crate r2d2_postgres;
 extern crate hex;
 extern crate url;
 extern crate uuid;
+extern crate validator;
+#[macro_use]
+extern crate validator_derive;
 
 pub mod config;
 pub mod common;
@@ -34,7 +37,6 @@ pub mod models;
 pub mod handlers;
 
 use rocket::Rocket;
-use rocket::fairing::AdHoc;
 use rocket_contrib::Template;
 use r2d2_postgres::{TlsMode, PostgresConnectionManager};
 use r2d2_redis::RedisConnectionManager;
@@ -54,8 +56,9 @@ pub fn create() -> Rocket {
     let cache = Cache::new(redis_manager).expect("failed to create cache");
 
     rocket::ignite()
-        .attach(AdHoc::on_response(fairings::ratelimit::on_response))
+        .attach(fairings::ratelimit::RateLimiter)
         .attach(Template::fairing())
+        .mount("/", routes![fairings::ratelimit::throttled])
         .mount(
             "/api/v1/",
             routes![
@@ -86,6 +89,10 @@ pub fn create() -> Rocket {
                    handlers::authorization::preview_authorization,
                    handlers::ticket::create_ticket,
                    handlers::ticket::update_ticket,
+                   handlers::token::create_token,
+                   handlers::totp::generate_recovery_codes,
+                   handlers::totp::regenerate_recovery_codes,
+                   handlers::totp::verify_recovery_code,
                ],
         )
         .manage(config)
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use postgres::Connection;
+
+use super::Error;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Application {
+    pub id: i64,
+    pub user_id: i64,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+fn from_row(row: postgres::rows::Row) -> Application {
+    Application {
+        id: row.get(0),
+        user_id: row.get(1),
+        name: row.get(2),
+        created_at: row.get(3),
+    }
+}
+
+pub fn select(
+    conn: &Connection,
+    user_id: i64,
+    offset: i64,
+    limit: i64,
+) -> Result<Vec<Application>, Error> {
+    let rows = conn.query(
+        "SELECT id, user_id, name, created_at FROM applications
+         WHERE user_id = $1 ORDER BY id LIMIT $2 OFFSET $3",
+        &[&user_id, &limit, &offset],
+    )?;
+
+    Ok(rows.iter().map(from_row).collect())
+}
+
+pub fn count(conn: &Connection, user_id: i64) -> Result<i64, Error> {
+    let rows = conn.query(
+        "SELECT count(*) FROM applications WHERE user_id = $1",
+        &[&user_id],
+    )?;
+
+    Ok(rows.iter().next().map(|row| row.get(0)).unwrap_or(0))
+}
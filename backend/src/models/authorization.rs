@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use postgres::Connection;
+
+use super::Error;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Authorization {
+    pub id: i64,
+    pub user_id: i64,
+    pub application_id: i64,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+fn from_row(row: postgres::rows::Row) -> Authorization {
+    Authorization {
+        id: row.get(0),
+        user_id: row.get(1),
+        application_id: row.get(2),
+        scopes: row.get(3),
+        created_at: row.get(4),
+    }
+}
+
+pub fn select(
+    conn: &Connection,
+    user_id: i64,
+    offset: i64,
+    limit: i64,
+) -> Result<Vec<Authorization>, Error> {
+    let rows = conn.query(
+        "SELECT id, user_id, application_id, scopes, created_at FROM authorizations
+         WHERE user_id = $1 ORDER BY id LIMIT $2 OFFSET $3",
+        &[&user_id, &limit, &offset],
+    )?;
+
+    Ok(rows.iter().map(from_row).collect())
+}
+
+pub fn count(conn: &Connection, user_id: i64) -> Result<i64, Error> {
+    let rows = conn.query(
+        "SELECT count(*) FROM authorizations WHERE user_id = $1",
+        &[&user_id],
+    )?;
+
+    Ok(rows.iter().next().map(|row| row.get(0)).unwrap_or(0))
+}
+
+pub fn select_one(conn: &Connection, id: i64) -> Result<Authorization, Error> {
+    let rows = conn.query(
+        "SELECT id, user_id, application_id, scopes, created_at FROM authorizations WHERE id = $1",
+        &[&id],
+    )?;
+
+    rows.iter().next().map(from_row).ok_or(Error::NotFound)
+}
+
+pub fn remove(conn: &Connection, id: i64) -> Result<Authorization, Error> {
+    let authorization = select_one(conn, id)?;
+    conn.execute("DELETE FROM authorizations WHERE id = $1", &[&id])?;
+
+    Ok(authorization)
+}
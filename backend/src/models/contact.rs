@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use postgres::Connection;
+
+use super::Error;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Contact {
+    pub id: i64,
+    pub user_id: i64,
+    pub kind: String,
+    pub value: String,
+    pub verified_at: Option<DateTime<Utc>>,
+}
+
+fn from_row(row: postgres::rows::Row) -> Contact {
+    Contact {
+        id: row.get(0),
+        user_id: row.get(1),
+        kind: row.get(2),
+        value: row.get(3),
+        verified_at: row.get(4),
+    }
+}
+
+pub fn select(
+    conn: &Connection,
+    user_id: i64,
+    offset: i64,
+    limit: i64,
+) -> Result<Vec<Contact>, Error> {
+    let rows = conn.query(
+        "SELECT id, user_id, kind, value, verified_at FROM contacts
+         WHERE user_id = $1 ORDER BY id LIMIT $2 OFFSET $3",
+        &[&user_id, &limit, &offset],
+    )?;
+
+    Ok(rows.iter().map(from_row).collect())
+}
+
+pub fn create(conn: &Connection, user_id: i64, kind: &str, value: &str) -> Result<Contact, Error> {
+    let rows = conn.query(
+        "INSERT INTO contacts (user_id, kind, value) VALUES ($1, $2, $3)
+         RETURNING id, user_id, kind, value, verified_at",
+        &[&user_id, &kind, &value],
+    )?;
+
+    rows.iter().next().map(from_row).ok_or(Error::NotFound)
+}
+
+pub fn count(conn: &Connection, user_id: i64) -> Result<i64, Error> {
+    let rows = conn.query(
+        "SELECT count(*) FROM contacts WHERE user_id = $1",
+        &[&user_id],
+    )?;
+
+    Ok(rows.iter().next().map(|row| row.get(0)).unwrap_or(0))
+}
@@ -0,0 +1,31 @@
+use postgres::Connection;
+
+use super::Error;
+
+#[derive(Serialize, Debug)]
+pub struct Group {
+    pub id: i64,
+    pub name: String,
+}
+
+pub fn select(conn: &Connection, offset: i64, limit: i64) -> Result<Vec<Group>, Error> {
+    let rows = conn.query(
+        "SELECT id, name FROM groups ORDER BY id LIMIT $1 OFFSET $2",
+        &[&limit, &offset],
+    )?;
+    let groups = rows
+        .iter()
+        .map(|row| Group {
+            id: row.get(0),
+            name: row.get(1),
+        })
+        .collect();
+
+    Ok(groups)
+}
+
+pub fn count(conn: &Connection) -> Result<i64, Error> {
+    let rows = conn.query("SELECT count(*) FROM groups", &[])?;
+
+    Ok(rows.iter().next().map(|row| row.get(0)).unwrap_or(0))
+}
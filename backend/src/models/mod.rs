@@ -0,0 +1,47 @@
+use std::error::Error as StdError;
+use std::fmt;
+
+use postgres;
+
+pub mod application;
+pub mod authorization;
+pub mod contact;
+pub mod group;
+pub mod totp_recovery_code;
+pub mod user;
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound,
+    QuotaLimit,
+    Database(postgres::error::Error),
+    InvalidParam(String, Box<StdError + Send + Sync>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::NotFound => write!(f, "NotFound"),
+            Error::QuotaLimit => write!(f, "QuotaLimit"),
+            Error::Database(ref err) => err.fmt(f),
+            Error::InvalidParam(ref field, ref err) => write!(f, "InvalidParam({}: {})", field, err),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::NotFound => "NotFound",
+            Error::QuotaLimit => "QuotaLimit",
+            Error::Database(ref err) => err.description(),
+            Error::InvalidParam(_, _) => "InvalidParam",
+        }
+    }
+}
+
+impl From<postgres::error::Error> for Error {
+    fn from(err: postgres::error::Error) -> Error {
+        Error::Database(err)
+    }
+}
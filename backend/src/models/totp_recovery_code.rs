@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use postgres::Connection;
+
+use super::Error;
+
+pub struct RecoveryCode {
+    pub id: i64,
+    pub user_id: i64,
+    pub code_hash: String,
+    pub consumed_at: Option<DateTime<Utc>>,
+}
+
+pub fn insert_many(conn: &Connection, user_id: i64, code_hashes: &[String]) -> Result<(), Error> {
+    for code_hash in code_hashes {
+        conn.execute(
+            "INSERT INTO totp_recovery_codes (user_id, code_hash) VALUES ($1, $2)",
+            &[&user_id, code_hash],
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn select_unconsumed(conn: &Connection, user_id: i64) -> Result<Vec<RecoveryCode>, Error> {
+    let rows = conn.query(
+        "SELECT id, user_id, code_hash, consumed_at FROM totp_recovery_codes
+         WHERE user_id = $1 AND consumed_at IS NULL",
+        &[&user_id],
+    )?;
+    let codes = rows
+        .iter()
+        .map(|row| RecoveryCode {
+            id: row.get(0),
+            user_id: row.get(1),
+            code_hash: row.get(2),
+            consumed_at: row.get(3),
+        })
+        .collect();
+
+    Ok(codes)
+}
+
+pub fn consume(conn: &Connection, id: i64) -> Result<(), Error> {
+    conn.execute(
+        "UPDATE totp_recovery_codes SET consumed_at = now() WHERE id = $1",
+        &[&id],
+    )?;
+
+    Ok(())
+}
+
+/// Deletes every recovery code for `user_id`, consumed or not. Used when
+/// regenerating a batch so the old ones can never be redeemed again.
+pub fn invalidate_all(conn: &Connection, user_id: i64) -> Result<(), Error> {
+    conn.execute("DELETE FROM totp_recovery_codes WHERE user_id = $1", &[&user_id])?;
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use postgres::Connection;
+
+use super::Error;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct User {
+    pub id: i64,
+    pub username: String,
+    #[serde(skip_serializing)]
+    pub password_hash: String,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub totp_enabled: bool,
+}
+
+pub fn create(conn: &Connection, username: &str, password_hash: &str) -> Result<User, Error> {
+    let row = conn.query(
+        "INSERT INTO users (username, password_hash) VALUES ($1, $2)
+         RETURNING id, username, password_hash, locked_until, totp_enabled",
+        &[&username, &password_hash],
+    )?;
+    let row = row.iter().next().ok_or(Error::NotFound)?;
+
+    Ok(User {
+        id: row.get(0),
+        username: row.get(1),
+        password_hash: row.get(2),
+        locked_until: row.get(3),
+        totp_enabled: row.get(4),
+    })
+}
+
+pub fn select_by_username(conn: &Connection, username: &str) -> Result<User, Error> {
+    let rows = conn.query(
+        "SELECT id, username, password_hash, locked_until, totp_enabled FROM users WHERE username = $1",
+        &[&username],
+    )?;
+    let row = rows.iter().next().ok_or(Error::NotFound)?;
+
+    Ok(User {
+        id: row.get(0),
+        username: row.get(1),
+        password_hash: row.get(2),
+        locked_until: row.get(3),
+        totp_enabled: row.get(4),
+    })
+}
+
+pub fn select_by_id(conn: &Connection, id: i64) -> Result<User, Error> {
+    let rows = conn.query(
+        "SELECT id, username, password_hash, locked_until, totp_enabled FROM users WHERE id = $1",
+        &[&id],
+    )?;
+    let row = rows.iter().next().ok_or(Error::NotFound)?;
+
+    Ok(User {
+        id: row.get(0),
+        username: row.get(1),
+        password_hash: row.get(2),
+        locked_until: row.get(3),
+        totp_enabled: row.get(4),
+    })
+}
+
+/// Sets or clears the account lockout deadline. `None` unlocks the account
+/// immediately, independent of the Redis failed-attempt counter in
+/// `handlers::user`.
+pub fn set_locked_until(
+    conn: &Connection,
+    id: i64,
+    locked_until: Option<DateTime<Utc>>,
+) -> Result<(), Error> {
+    conn.execute(
+        "UPDATE users SET locked_until = $1 WHERE id = $2",
+        &[&locked_until, &id],
+    )?;
+
+    Ok(())
+}